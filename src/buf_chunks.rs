@@ -0,0 +1,81 @@
+use bytes::{Buf, Bytes};
+
+/// A streaming, byte-budgeted chunker over an [`impl Buf`](bytes::Buf) source.
+///
+/// Unlike [`ByteChunks`](crate::ByteChunks), which splits an already-collected
+/// `&[T]`, `BufChunks` drains its source incrementally via
+/// [`Buf::copy_to_bytes`], so it works directly against streamed/incremental
+/// byte sources (e.g. a `BytesMut` accumulator filled from a socket) without
+/// requiring the whole input to be materialized first.
+pub struct BufChunks<B> {
+    buf: B,
+    chunk_size: usize,
+    element_size: usize,
+}
+
+impl<B: Buf> BufChunks<B> {
+    /// Splits `buf` into chunks of at most `chunk_size` bytes.
+    pub fn new(buf: B, chunk_size: usize) -> Self {
+        Self {
+            buf,
+            chunk_size,
+            element_size: 1,
+        }
+    }
+
+    /// Splits `buf` into chunks of at most `chunk_size` bytes, never slicing
+    /// through the middle of a fixed-width `element_size` record.
+    ///
+    /// If `chunk_size` is smaller than `element_size`, each chunk holds one
+    /// whole element and so exceeds `chunk_size`. Trailing bytes that do not
+    /// fill a whole element are yielded as a final, shorter chunk.
+    pub fn with_element_size(buf: B, chunk_size: usize, element_size: usize) -> Self {
+        Self {
+            buf,
+            chunk_size,
+            element_size,
+        }
+    }
+}
+
+impl<B: Buf> Iterator for BufChunks<B> {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        let remaining = self.buf.remaining();
+        if remaining == 0 {
+            return None;
+        }
+
+        let mut n = self.chunk_size.min(remaining);
+        if self.element_size > 1 {
+            n -= n % self.element_size;
+            if n == 0 {
+                // The budget is smaller than a single element, or less than
+                // one element remains; take up to one element anyway rather
+                // than yielding an empty chunk.
+                n = remaining.min(self.element_size);
+            }
+        }
+
+        if n == 0 {
+            // A zero byte budget can never make progress; stop instead of
+            // yielding empty chunks forever.
+            return None;
+        }
+
+        Some(self.buf.copy_to_bytes(n))
+    }
+}
+
+pub trait BufChunked: Buf + Sized {
+    fn buf_chunks(self, chunk_size: usize) -> BufChunks<Self> {
+        BufChunks::new(self, chunk_size)
+    }
+
+    fn buf_chunks_by_element(self, chunk_size: usize, element_size: usize) -> BufChunks<Self> {
+        BufChunks::with_element_size(self, chunk_size, element_size)
+    }
+}
+
+impl<B: Buf> BufChunked for B {}
@@ -0,0 +1,70 @@
+use crate::metric::{self, Metric};
+use crate::SizeInBytes;
+
+/// A [`ByteChunks`](crate::ByteChunks) variant whose chunk budget `N` is a
+/// compile-time constant rather than a runtime field.
+///
+/// This is the shape embedded/`no_std` callers reach for: splitting a
+/// `&[u8]` payload into MTU-sized frames where the frame size is known at
+/// compile time, following the const-generics style used by crates such as
+/// `heapless`.
+pub struct ConstByteChunks<'a, T: 'a, const N: usize, M = metric::Bytes> {
+    v: &'a [T],
+    metric: M,
+}
+
+impl<'a, T: 'a, const N: usize> ConstByteChunks<'a, T, N, metric::Bytes>
+where
+    T: SizeInBytes,
+{
+    pub fn new(slice: &'a [T]) -> Self {
+        Self {
+            v: slice,
+            metric: metric::Bytes,
+        }
+    }
+}
+
+impl<'a, T: 'a, const N: usize, M> ConstByteChunks<'a, T, N, M>
+where
+    M: Metric<T>,
+{
+    pub fn with_metric(slice: &'a [T], metric: M) -> Self {
+        Self { v: slice, metric }
+    }
+
+    fn split_index_from(&self, start: usize) -> usize {
+        let mut measured = 0;
+        let mut index = start;
+        while let Some(d) = self.v.get(index) {
+            let size_of_next = self.metric.measure(d);
+            if size_of_next > N {
+                panic!("Chunk is larger than {} units", N);
+            } else if measured + size_of_next > N {
+                break;
+            } else {
+                measured += size_of_next;
+                index += 1;
+            }
+        }
+        index
+    }
+}
+
+impl<'a, T, const N: usize, M> Iterator for ConstByteChunks<'a, T, N, M>
+where
+    M: Metric<T>,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let chunksz = self.split_index_from(0);
+            let (fst, snd) = self.v.split_at(chunksz);
+            self.v = snd;
+            Some(fst)
+        }
+    }
+}
@@ -1,30 +1,71 @@
-use bytes::Bytes;
-use std::fmt::Display;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub struct ByteChunks<'a, T: 'a> {
+#[cfg(feature = "bytes")]
+use bytes::{Buf, Bytes, BytesMut};
+
+#[cfg(feature = "bytes")]
+mod buf_chunks;
+mod const_chunks;
+pub mod metric;
+mod try_chunks;
+
+#[cfg(feature = "bytes")]
+pub use buf_chunks::{BufChunked, BufChunks};
+pub use const_chunks::ConstByteChunks;
+pub use metric::Metric;
+pub use try_chunks::{ChunkError, OversizePolicy, TryByteChunked, TryByteChunks};
+
+pub struct ByteChunks<'a, T: 'a, M = metric::Bytes> {
     v: &'a [T],
     chunk_byte_size: usize,
+    metric: M,
 }
 
 pub trait SizeInBytes {
     fn bytes_size(&self) -> usize;
 }
 
+#[cfg(feature = "std")]
 impl SizeInBytes for String {
     fn bytes_size(&self) -> usize {
-        let bytes: Bytes = self.to_owned().into();
-        bytes.len()
+        self.len()
+    }
+}
+
+impl SizeInBytes for &str {
+    fn bytes_size(&self) -> usize {
+        self.len()
     }
 }
 
-impl<'a> SizeInBytes for &'a str {
+impl SizeInBytes for &[u8] {
     fn bytes_size(&self) -> usize {
-        let bytes: Bytes = self.to_string().into();
-        bytes.len()
+        self.len()
     }
 }
 
-impl<'a, T: 'a> ByteChunks<'a, T>
+#[cfg(feature = "std")]
+impl SizeInBytes for Vec<u8> {
+    fn bytes_size(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl SizeInBytes for Bytes {
+    fn bytes_size(&self) -> usize {
+        self.remaining()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl SizeInBytes for BytesMut {
+    fn bytes_size(&self) -> usize {
+        self.remaining()
+    }
+}
+
+impl<'a, T: 'a> ByteChunks<'a, T, metric::Bytes>
 where
     T: SizeInBytes,
 {
@@ -32,23 +73,37 @@ where
         Self {
             v: slice,
             chunk_byte_size: size,
+            metric: metric::Bytes,
         }
     }
+}
 
-    fn next_split_index(&mut self) -> usize {
-        let mut byte_count = 0;
-        let mut index = 0;
+impl<'a, T: 'a, M> ByteChunks<'a, T, M>
+where
+    M: Metric<T>,
+{
+    pub fn with_metric(slice: &'a [T], size: usize, metric: M) -> Self {
+        Self {
+            v: slice,
+            chunk_byte_size: size,
+            metric,
+        }
+    }
+
+    fn split_index_from(&self, start: usize) -> usize {
+        let mut measured = 0;
+        let mut index = start;
         loop {
             let next = self.v.get(index);
             match next {
                 Some(d) => {
-                    let size_of_next = d.bytes_size();
+                    let size_of_next = self.metric.measure(d);
                     if size_of_next > self.chunk_byte_size {
-                        panic!("Chunk is larger than {} bytes", self.chunk_byte_size);
-                    } else if byte_count + size_of_next > self.chunk_byte_size {
+                        panic!("Chunk is larger than {} units", self.chunk_byte_size);
+                    } else if measured + size_of_next > self.chunk_byte_size {
                         break;
                     } else {
-                        byte_count += size_of_next;
+                        measured += size_of_next;
                         index += 1;
                     }
                 }
@@ -57,11 +112,27 @@ where
         }
         index
     }
+
+    fn next_split_index(&mut self) -> usize {
+        self.split_index_from(0)
+    }
+
+    /// Index at which the final chunk (the one `next_back` would yield) begins.
+    fn rsplit_index(&self) -> usize {
+        let mut start = 0;
+        loop {
+            let next = self.split_index_from(start);
+            if next >= self.v.len() {
+                return start;
+            }
+            start = next;
+        }
+    }
 }
 
-impl<'a, T> Iterator for ByteChunks<'a, T>
+impl<'a, T, M> Iterator for ByteChunks<'a, T, M>
 where
-    T: SizeInBytes,
+    M: Metric<T>,
 {
     type Item = &'a [T];
 
@@ -75,8 +146,46 @@ where
             Some(fst)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, M> DoubleEndedIterator for ByteChunks<'a, T, M>
+where
+    M: Metric<T>,
+{
+    fn next_back(&mut self) -> Option<&'a [T]> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let start = self.rsplit_index();
+            let (fst, snd) = self.v.split_at(start);
+            self.v = fst;
+            Some(snd)
+        }
+    }
 }
 
+impl<'a, T, M> ExactSizeIterator for ByteChunks<'a, T, M>
+where
+    M: Metric<T>,
+{
+    fn len(&self) -> usize {
+        let mut count = 0;
+        let mut start = 0;
+        while start < self.v.len() {
+            start = self.split_index_from(start);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<'a, T, M> core::iter::FusedIterator for ByteChunks<'a, T, M> where M: Metric<T> {}
+
 pub trait ByteChunked<'a, T> {
     fn byte_chunks(&self, chunk_byte_size: usize) -> ByteChunks<'_, T>;
 }
@@ -98,6 +207,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<T> ByteChunked<'_, T> for Vec<T>
 where
     T: SizeInBytes,
@@ -107,6 +217,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<T> SafeByteChunkedMut<'_, T> for Vec<T>
 where
     T: SizeInBytes,
@@ -157,7 +268,7 @@ mod tests {
 
     #[test]
     fn creates_chunks_string() {
-        let data: Vec<String> = (vec!["Hello", "There", "Best", "Worl", "D", "A"])
+        let data: Vec<String> = ["Hello", "There", "Best", "Worl", "D", "A"]
             .iter()
             .map(|&x| String::from(x))
             .collect();
@@ -190,7 +301,7 @@ mod tests {
     //"ラウトは難しいです！" == 30 bytes
     #[test]
     fn special_chars_are_sized_with_string() {
-        let data: Vec<String> = vec!["ラウ", "トは", "難し", "いで", "す！"]
+        let data: Vec<String> = ["ラウ", "トは", "難し", "いで", "す！"]
             .iter()
             .map(|&x| String::from(x))
             .collect();
@@ -241,7 +352,7 @@ mod tests {
 
     #[test]
     fn strings_that_are_too_large_are_skipped() {
-        let mut data: Vec<String> = vec!["Hello", "There"]
+        let mut data: Vec<String> = ["Hello", "There"]
             .iter()
             .map(|&x| String::from(x))
             .collect();
@@ -250,4 +361,229 @@ mod tests {
         let next = chunk_iter.next();
         assert_eq!(None, next);
     }
+
+    #[test]
+    fn creates_chunks_bytes() {
+        use bytes::Bytes;
+
+        let data: Vec<Bytes> = ["Hello", "There", "Best", "Worl", "D", "A"]
+            .iter()
+            .map(|&x| Bytes::from(x))
+            .collect();
+
+        let mut chunk_iter = data.byte_chunks(10);
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(4, next.len());
+        }
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    fn creates_chunks_with_chars_metric() {
+        use crate::metric::Chars;
+        use crate::ByteChunks;
+
+        // "ラウトは難しいです！" is 10 chars but 30 bytes.
+        let data: Vec<&str> = vec!["ラウ", "トは", "難し", "いで", "す！"];
+
+        let mut chunk_iter = ByteChunks::with_metric(data.as_slice(), 4, Chars);
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(1, next.len());
+        }
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    #[cfg(feature = "graphemes")]
+    fn creates_chunks_with_graphemes_metric() {
+        use crate::metric::Graphemes;
+        use crate::ByteChunks;
+
+        // Flag emoji are made of two scalar values but one grapheme cluster.
+        let data: Vec<&str> = vec!["🇯🇵", "🇺🇸", "a", "b"];
+
+        let mut chunk_iter = ByteChunks::with_metric(data.as_slice(), 2, Graphemes);
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    fn try_chunks_errors_on_oversized_element_by_default() {
+        use crate::{ChunkError, OversizePolicy, TryByteChunked};
+
+        let data: Vec<&str> = vec!["Hello", "There"];
+        let mut chunk_iter = data.try_byte_chunks(3, OversizePolicy::default());
+
+        let next = chunk_iter.next();
+        assert_eq!(Some(Err(ChunkError::Oversized { index: 0, size: 5 })), next);
+    }
+
+    #[test]
+    fn try_chunks_skips_oversized_elements() {
+        use crate::{OversizePolicy, TryByteChunked};
+
+        let data: Vec<&str> = vec!["Hello", "There", "Hi"];
+        let mut chunk_iter = data.try_byte_chunks(3, OversizePolicy::Skip);
+
+        let next = chunk_iter.next();
+        assert_eq!(Some(Ok(["Hi"].as_slice())), next);
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    fn try_chunks_isolates_oversized_elements() {
+        use crate::{OversizePolicy, TryByteChunked};
+
+        let data: Vec<&str> = vec!["Hello", "Hi"];
+        let mut chunk_iter = data.try_byte_chunks(3, OversizePolicy::Isolate);
+
+        let next = chunk_iter.next();
+        assert_eq!(Some(Ok(["Hello"].as_slice())), next);
+
+        let next = chunk_iter.next();
+        assert_eq!(Some(Ok(["Hi"].as_slice())), next);
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    fn iterates_chunks_in_reverse() {
+        let data: Vec<&str> = vec!["Hello", "There", "Best", "Worl", "D", "A"];
+        let chunk_iter = data.byte_chunks(10);
+        let chunks: Vec<&[&str]> = chunk_iter.rev().collect();
+
+        assert_eq!(2, chunks.len());
+        assert_eq!(4, chunks[0].len());
+        assert_eq!(2, chunks[1].len());
+    }
+
+    #[test]
+    fn reports_exact_len_and_size_hint() {
+        let data: Vec<&str> = vec!["Hello", "There", "Best", "Worl", "D", "A"];
+        let chunk_iter = data.byte_chunks(10);
+
+        assert_eq!(2, chunk_iter.len());
+        assert_eq!((2, Some(2)), chunk_iter.size_hint());
+    }
+
+    #[test]
+    fn streams_chunks_from_buf() {
+        use crate::BufChunked;
+        use bytes::Bytes;
+
+        let data = Bytes::from_static(b"HelloThereBestWorlDA");
+        let chunks: Vec<Bytes> = data.buf_chunks(6).collect();
+
+        assert_eq!(
+            vec![
+                Bytes::from_static(b"HelloT"),
+                Bytes::from_static(b"hereBe"),
+                Bytes::from_static(b"stWorl"),
+                Bytes::from_static(b"DA"),
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn streams_chunks_from_buf_aligned_to_elements() {
+        use crate::BufChunked;
+        use bytes::Bytes;
+
+        // 5 elements of 4 bytes each, budgeted 10 bytes per chunk: the
+        // aligned variant must never split a 4-byte element.
+        let data = Bytes::from_static(b"aaaabbbbccccddddeeee");
+        let chunks: Vec<Bytes> = data.buf_chunks_by_element(10, 4).collect();
+
+        assert_eq!(
+            vec![
+                Bytes::from_static(b"aaaabbbb"),
+                Bytes::from_static(b"ccccdddd"),
+                Bytes::from_static(b"eeee"),
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn creates_chunks_with_const_generic_size() {
+        use crate::ConstByteChunks;
+
+        let data: [&[u8]; 6] = [b"Hello", b"There", b"Best", b"Worl", b"D", b"A"];
+
+        let mut chunk_iter = ConstByteChunks::<_, 10>::new(&data);
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(4, next.len());
+        }
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
+
+    #[test]
+    fn creates_chunks_vec_u8() {
+        let data: Vec<Vec<u8>> = ["Hello", "There", "Best", "Worl", "D", "A"]
+            .iter()
+            .map(|&x| x.as_bytes().to_vec())
+            .collect();
+
+        let mut chunk_iter = data.byte_chunks(10);
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(2, next.len());
+        }
+
+        if let Some(next) = chunk_iter.next() {
+            println!("{:?}", next);
+            assert_eq!(4, next.len());
+        }
+
+        let next = chunk_iter.next();
+        assert_eq!(None, next);
+    }
 }
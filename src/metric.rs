@@ -0,0 +1,62 @@
+use crate::SizeInBytes;
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Defines how a single item's "size" is counted towards a chunk's budget.
+///
+/// `ByteChunks` is generic over `Metric` so the same splitting logic can
+/// budget by raw bytes, `char`s, or extended grapheme clusters.
+pub trait Metric<T> {
+    fn measure(&self, item: &T) -> usize;
+}
+
+/// Measures items by their length in bytes, via [`SizeInBytes`].
+///
+/// This is the default metric used by [`ByteChunks::new`](crate::ByteChunks::new).
+pub struct Bytes;
+
+impl<T> Metric<T> for Bytes
+where
+    T: SizeInBytes,
+{
+    fn measure(&self, item: &T) -> usize {
+        item.bytes_size()
+    }
+}
+
+/// Measures items by their `char` count.
+pub struct Chars;
+
+#[cfg(feature = "std")]
+impl Metric<String> for Chars {
+    fn measure(&self, item: &String) -> usize {
+        item.chars().count()
+    }
+}
+
+impl<'a> Metric<&'a str> for Chars {
+    fn measure(&self, item: &&'a str) -> usize {
+        item.chars().count()
+    }
+}
+
+/// Measures items by their extended grapheme cluster count, so that e.g.
+/// combining marks or emoji with modifiers count as a single unit.
+///
+/// Requires the `graphemes` feature, which pulls in `unicode-segmentation`.
+#[cfg(feature = "graphemes")]
+pub struct Graphemes;
+
+#[cfg(all(feature = "std", feature = "graphemes"))]
+impl Metric<String> for Graphemes {
+    fn measure(&self, item: &String) -> usize {
+        item.graphemes(true).count()
+    }
+}
+
+#[cfg(feature = "graphemes")]
+impl<'a> Metric<&'a str> for Graphemes {
+    fn measure(&self, item: &&'a str) -> usize {
+        item.graphemes(true).count()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::metric::{self, Metric};
+use crate::SizeInBytes;
+use core::fmt;
+
+/// What to do with an element that alone exceeds the configured chunk size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OversizePolicy {
+    /// Yield a [`ChunkError::Oversized`] for the offending element (default).
+    #[default]
+    Error,
+    /// Drop the offending element and continue chunking the rest.
+    Skip,
+    /// Emit the offending element as its own single-item chunk, even though
+    /// it breaks the configured budget.
+    Isolate,
+}
+
+/// Error produced by [`TryByteChunks`] under [`OversizePolicy::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkError {
+    /// The element at `index` measures `size`, which alone exceeds the
+    /// configured chunk size.
+    Oversized { index: usize, size: usize },
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::Oversized { index, size } => write!(
+                f,
+                "element at index {} measures {} which exceeds the chunk size",
+                index, size
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChunkError {}
+
+/// A fallible, policy-driven alternative to [`ByteChunks`](crate::ByteChunks)
+/// that never panics on an oversized element.
+pub struct TryByteChunks<'a, T: 'a, M = metric::Bytes> {
+    v: &'a [T],
+    chunk_size: usize,
+    metric: M,
+    policy: OversizePolicy,
+    consumed: usize,
+}
+
+impl<'a, T: 'a> TryByteChunks<'a, T, metric::Bytes>
+where
+    T: SizeInBytes,
+{
+    pub fn new(slice: &'a [T], size: usize, policy: OversizePolicy) -> Self {
+        Self {
+            v: slice,
+            chunk_size: size,
+            metric: metric::Bytes,
+            policy,
+            consumed: 0,
+        }
+    }
+}
+
+impl<'a, T: 'a, M> TryByteChunks<'a, T, M>
+where
+    M: Metric<T>,
+{
+    pub fn with_metric(slice: &'a [T], size: usize, metric: M, policy: OversizePolicy) -> Self {
+        Self {
+            v: slice,
+            chunk_size: size,
+            metric,
+            policy,
+            consumed: 0,
+        }
+    }
+
+    fn next_split_index(&self) -> usize {
+        let mut measured = 0;
+        let mut index = 0;
+        while let Some(item) = self.v.get(index) {
+            let size_of_next = self.metric.measure(item);
+            if size_of_next > self.chunk_size || measured + size_of_next > self.chunk_size {
+                break;
+            } else {
+                measured += size_of_next;
+                index += 1;
+            }
+        }
+        index
+    }
+}
+
+impl<'a, T, M> Iterator for TryByteChunks<'a, T, M>
+where
+    M: Metric<T>,
+{
+    type Item = Result<&'a [T], ChunkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let first = self.v.first()?;
+            let first_size = self.metric.measure(first);
+            if first_size <= self.chunk_size {
+                break;
+            }
+
+            match self.policy {
+                OversizePolicy::Error => {
+                    let err = ChunkError::Oversized {
+                        index: self.consumed,
+                        size: first_size,
+                    };
+                    self.v = &self.v[1..];
+                    self.consumed += 1;
+                    return Some(Err(err));
+                }
+                OversizePolicy::Skip => {
+                    self.v = &self.v[1..];
+                    self.consumed += 1;
+                }
+                OversizePolicy::Isolate => {
+                    let (fst, snd) = self.v.split_at(1);
+                    self.v = snd;
+                    self.consumed += 1;
+                    return Some(Ok(fst));
+                }
+            }
+        }
+
+        let chunksz = self.next_split_index();
+        let (fst, snd) = self.v.split_at(chunksz);
+        self.v = snd;
+        self.consumed += chunksz;
+        Some(Ok(fst))
+    }
+}
+
+pub trait TryByteChunked<'a, T> {
+    fn try_byte_chunks(
+        &self,
+        chunk_byte_size: usize,
+        policy: OversizePolicy,
+    ) -> TryByteChunks<'_, T>;
+}
+
+impl<T> TryByteChunked<'_, T> for [T]
+where
+    T: SizeInBytes,
+{
+    fn try_byte_chunks(
+        &self,
+        chunk_byte_size: usize,
+        policy: OversizePolicy,
+    ) -> TryByteChunks<'_, T> {
+        TryByteChunks::new(self, chunk_byte_size, policy)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> TryByteChunked<'_, T> for Vec<T>
+where
+    T: SizeInBytes,
+{
+    fn try_byte_chunks(
+        &self,
+        chunk_byte_size: usize,
+        policy: OversizePolicy,
+    ) -> TryByteChunks<'_, T> {
+        TryByteChunks::new(self.as_slice(), chunk_byte_size, policy)
+    }
+}